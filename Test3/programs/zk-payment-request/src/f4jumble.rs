@@ -0,0 +1,145 @@
+//! F4Jumble byte-mixing layer (Zcash unified-address style) for the
+//! `[scan_pub || spend_pub || ephemeral_pubkey]` payload. The program jumbles the
+//! payload when a request is created and emits it in `StealthAddressEmitted`; clients
+//! bech32m-encode it for display and invert it with [`f4jumble_inv`]. It is a four-round unkeyed Feistel permutation built
+//! from BLAKE2b, so flipping a single bit anywhere in the encoded address avalanches
+//! across the whole string -- catching truncation or a typo'd character that plain
+//! concatenation would let through silently.
+
+use blake2b_simd::Params;
+
+/// BLAKE2b's maximum digest length; also caps how much output a single call to
+/// `G`/`H` can produce before we have to hash another block.
+const MAX_BLAKE2B_OUTPUT: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum F4JumbleError {
+    /// F4Jumble needs at least one byte on each side of the Feistel split.
+    MessageTooShort { len: usize },
+    /// An unjumbled stealth payload is not the expected 96 bytes.
+    InvalidPayloadLength { len: usize },
+}
+
+/// Length of the left half of an `L`-byte message: `min(L_H, ceil(L / 2))`.
+fn left_length(total_len: usize) -> usize {
+    core::cmp::min(MAX_BLAKE2B_OUTPUT, (total_len + 1) / 2)
+}
+
+/// `G(i, ·)` or `H(i, ·)` expanded to `out_len` bytes: BLAKE2b is only a fixed/bounded
+/// output function, so for `out_len > 64` we hash additional blocks, each with a
+/// personalization that also encodes the block index.
+fn expand(kind: u8, round: u8, input: &[u8], out_len: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(out_len);
+    let mut block: u32 = 0;
+    while out.len() < out_len {
+        let remaining = out_len - out.len();
+        let block_len = remaining.min(MAX_BLAKE2B_OUTPUT);
+
+        // Personalization: 1 byte round function (G/H), 1 byte round index,
+        // 4 bytes little-endian block index -- this is `G_PERS(i, j)` / `H_PERS(i)`.
+        let mut personal = [0u8; 16];
+        personal[0] = kind;
+        personal[1] = round;
+        personal[2..6].copy_from_slice(&block.to_le_bytes());
+
+        let hash = Params::new()
+            .hash_length(block_len)
+            .personal(&personal)
+            .to_state()
+            .update(input)
+            .finalize();
+        out.extend_from_slice(hash.as_bytes());
+        block += 1;
+    }
+    out
+}
+
+fn g(round: u8, left: &[u8], out_len: usize) -> Vec<u8> {
+    expand(b'G', round, left, out_len)
+}
+
+fn h(round: u8, right: &[u8], out_len: usize) -> Vec<u8> {
+    expand(b'H', round, right, out_len)
+}
+
+fn xor_into(buf: &mut [u8], mask: &[u8]) {
+    for (byte, mask_byte) in buf.iter_mut().zip(mask) {
+        *byte ^= mask_byte;
+    }
+}
+
+fn split(message: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let split_at = left_length(message.len());
+    (message[..split_at].to_vec(), message[split_at..].to_vec())
+}
+
+/// Apply the four-round Feistel permutation: `right ^= G_1(left); left ^= H_2(right);
+/// right ^= G_3(left); left ^= H_4(right)`.
+pub fn f4jumble(message: &[u8]) -> Result<Vec<u8>, F4JumbleError> {
+    require_long_enough(message)?;
+    let (mut left, mut right) = split(message);
+
+    xor_into(&mut right, &g(1, &left, right.len()));
+    xor_into(&mut left, &h(2, &right, left.len()));
+    xor_into(&mut right, &g(3, &left, right.len()));
+    xor_into(&mut left, &h(4, &right, left.len()));
+
+    let mut out = left;
+    out.extend_from_slice(&right);
+    Ok(out)
+}
+
+/// Invert [`f4jumble`] by undoing the same four XOR steps in reverse order.
+pub fn f4jumble_inv(message: &[u8]) -> Result<Vec<u8>, F4JumbleError> {
+    require_long_enough(message)?;
+    let (mut left, mut right) = split(message);
+
+    xor_into(&mut left, &h(4, &right, left.len()));
+    xor_into(&mut right, &g(3, &left, right.len()));
+    xor_into(&mut left, &h(2, &right, left.len()));
+    xor_into(&mut right, &g(1, &left, right.len()));
+
+    let mut out = left;
+    out.extend_from_slice(&right);
+    Ok(out)
+}
+
+fn require_long_enough(message: &[u8]) -> Result<(), F4JumbleError> {
+    if message.len() < 2 {
+        return Err(F4JumbleError::MessageTooShort { len: message.len() });
+    }
+    Ok(())
+}
+
+/// Jumble the receiver's scan/spend keys together with the payer's ephemeral key into
+/// the single blob clients bech32m-encode as a unified stealth address.
+pub fn jumble_stealth_payload(
+    scan_pubkey: &[u8; 32],
+    spend_pubkey: &[u8; 32],
+    ephemeral_pubkey: &[u8; 32],
+) -> Result<Vec<u8>, F4JumbleError> {
+    let mut payload = Vec::with_capacity(96);
+    payload.extend_from_slice(scan_pubkey);
+    payload.extend_from_slice(spend_pubkey);
+    payload.extend_from_slice(ephemeral_pubkey);
+    f4jumble(&payload)
+}
+
+/// Recover `(scan_pubkey, spend_pubkey, ephemeral_pubkey)` from a jumbled payload
+/// produced by [`jumble_stealth_payload`].
+pub fn unjumble_stealth_payload(
+    jumbled: &[u8],
+) -> Result<([u8; 32], [u8; 32], [u8; 32]), F4JumbleError> {
+    let payload = f4jumble_inv(jumbled)?;
+    if payload.len() != 96 {
+        return Err(F4JumbleError::InvalidPayloadLength { len: payload.len() });
+    }
+
+    let mut scan_pubkey = [0u8; 32];
+    let mut spend_pubkey = [0u8; 32];
+    let mut ephemeral_pubkey = [0u8; 32];
+    scan_pubkey.copy_from_slice(&payload[0..32]);
+    spend_pubkey.copy_from_slice(&payload[32..64]);
+    ephemeral_pubkey.copy_from_slice(&payload[64..96]);
+    Ok((scan_pubkey, spend_pubkey, ephemeral_pubkey))
+}
@@ -1,15 +1,21 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
 use anchor_lang::system_program;
-use ark_ff::BigInteger256;
 use std::convert::TryInto;
 use bulletproofs::{BulletproofGens, PedersenGens, RangeProof};
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
 use curve25519_dalek::scalar::Scalar;
 use merlin::Transcript;
-use rand::Rng;
-use sha3::Sha3_256;
 use hkdf::Hkdf;
 use sha2::{Sha256 as Sha2_256, Digest};
 
+mod f4jumble;
+use f4jumble::jumble_stealth_payload;
+
 declare_id!("GDjvp1n9QMKKF1gtxFmCQLY3xFxu18ZLbmZBLaFN3kuq");
 
 #[program]
@@ -17,7 +23,26 @@ pub mod zk_payment_request {
     use super::*;
 
     /// Create a zero-knowledge payment request with amount commitment and range proof
-    /// Receiver creates a unique request ID with hidden amount details and stealth address
+    /// Receiver creates a unique request ID with hidden amount details and stealth address.
+    ///
+    /// `scan_pubkey`/`spend_pubkey` are the receiver's dual stealth keys (Zcash-style).
+    /// `ephemeral_pubkey` is the payer's one-time `r*G` and `stealth_address` is the
+    /// one-time address `spend_pub + H(r*scan_pub)*G` the payer derived off-chain --
+    /// the program cannot recompute that derivation itself since it never sees `r`,
+    /// so it only checks that every published point actually lies on the curve.
+    ///
+    /// `encrypted_memo` is a Zcash-style padded note: the payer ChaCha20-Poly1305
+    /// encrypts the memo under a key derived from the same ECDH shared secret
+    /// (`r*scan_pub`) used for the stealth address, so only the receiver -- who
+    /// can recompute that secret from `scan_secret*ephemeral_pubkey` -- can decrypt
+    /// it off-chain in `sweep_zk_funds`. The program never sees the plaintext.
+    ///
+    /// `authorized_signers`/`threshold` make the receiver a Zcash-style `m`-of-`n`
+    /// multisig: each entry in `authorized_signers` carries its own independent
+    /// scan/spend/ephemeral key share and one-time stealth sub-address, so sweeping
+    /// requires `threshold` distinct signers to each call `approve_zk_sweep` and
+    /// prove ownership of *their own* share before `sweep_zk_funds` will release
+    /// the escrow.
     pub fn create_zk_pay_request(
         ctx: Context<CreateZkPayRequest>,
         request_id: u64,
@@ -25,16 +50,24 @@ pub mod zk_payment_request {
         amount_range_proof: Vec<u8>,
         min_amount: u64,
         max_amount: u64,
+        scan_pubkey: [u8; 32],
+        spend_pubkey: [u8; 32],
         ephemeral_pubkey: [u8; 32],
+        stealth_address: [u8; 32],
+        encrypted_memo: [u8; 580],
+        authorized_signers: Vec<AuthorizedSigner>,
+        threshold: u8,
     ) -> Result<()> {
-        let pay_request = &mut ctx.accounts.pay_request;
+        // Sanity-check that every stealth key is a valid Ristretto point before storing it.
+        decompress_ristretto(&scan_pubkey)?;
+        decompress_ristretto(&spend_pubkey)?;
+        decompress_ristretto(&ephemeral_pubkey)?;
+        decompress_ristretto(&stealth_address)?;
 
-        // Generate stealth address using HKDF
-        let stealth_address = generate_stealth_address(
-            &ctx.accounts.receiver.key(),
-            request_id,
-            &ephemeral_pubkey,
-        )?;
+        require!(
+            amount_range_proof.len() <= MAX_RANGE_PROOF_LEN,
+            ZkPaymentRequestError::RangeProofTooLarge
+        );
 
         // Verify the bulletproof range proof before storing
         verify_bulletproof_range_proof(
@@ -42,25 +75,63 @@ pub mod zk_payment_request {
             &amount_range_proof,
             min_amount,
             max_amount,
+            request_id,
         )?;
 
+        require!(
+            !authorized_signers.is_empty() && authorized_signers.len() <= MAX_AUTHORIZED_SIGNERS,
+            ZkPaymentRequestError::InvalidThreshold
+        );
+        require!(
+            threshold >= 1 && (threshold as usize) <= authorized_signers.len(),
+            ZkPaymentRequestError::InvalidThreshold
+        );
+        // Every signer's key share must itself be a valid curve point before it's stored.
+        for signer in authorized_signers.iter() {
+            decompress_ristretto(&signer.scan_pubkey)?;
+            decompress_ristretto(&signer.spend_pubkey)?;
+            decompress_ristretto(&signer.ephemeral_pubkey)?;
+            decompress_ristretto(&signer.stealth_address.to_bytes())?;
+        }
+
+        let pay_request = &mut ctx.accounts.pay_request;
+
         // Initialize the ZK payment request account
         pay_request.receiver = ctx.accounts.receiver.key();
         pay_request.request_id = request_id;
         pay_request.amount_commitment = amount_commitment;
         pay_request.amount_range_proof = amount_range_proof;
-        pay_request.stealth_address = stealth_address;
+        pay_request.scan_pubkey = scan_pubkey;
+        pay_request.spend_pubkey = spend_pubkey;
+        pay_request.ephemeral_pubkey = ephemeral_pubkey;
+        pay_request.stealth_address = Pubkey::new_from_array(stealth_address);
+        pay_request.encrypted_memo = encrypted_memo;
+        pay_request.authorized_signers = authorized_signers;
+        pay_request.threshold = threshold;
+        pay_request.approvals_bitmap = 0;
+        pay_request.approved_count = 0;
         pay_request.min_amount = min_amount;
         pay_request.max_amount = max_amount;
         pay_request.is_settled = false;
         pay_request.is_swept = false;
         pay_request.settlement_commitment = [0u8; 32];
-        pay_request.ownership_proof = Vec::new();
+
+        let jumbled_address = jumble_stealth_payload(
+            &pay_request.scan_pubkey,
+            &pay_request.spend_pubkey,
+            &pay_request.ephemeral_pubkey,
+        )
+        .map_err(|_| ZkPaymentRequestError::InvalidStealthKey)?;
+
+        emit!(StealthAddressEmitted {
+            request_id,
+            jumbled_address: jumbled_address.clone(),
+        });
 
         msg!(
             "ZK Payment request created: ID={}, Stealth Address={}",
             request_id,
-            stealth_address
+            pay_request.stealth_address
         );
 
         Ok(())
@@ -91,7 +162,7 @@ pub mod zk_payment_request {
         verify_bulletproof_payment(
             &pay_request.amount_commitment,
             amount,
-            &pay_request.amount_range_proof,
+            &payment_proof,
         )?;
 
         // Transfer SOL from payer to stealth escrow PDA
@@ -129,36 +200,81 @@ pub mod zk_payment_request {
         Ok(())
     }
 
-    /// Sweep funds with zero-knowledge ownership proof
-    /// Receiver proves ownership of stealth address without revealing identity
-    pub fn sweep_zk_funds(
-        ctx: Context<SweepZkFunds>,
+    /// Record one authorized signer's approval to sweep the escrow. Each of the
+    /// `threshold` required approvers holds their own key share (a distinct
+    /// scan/spend/ephemeral triple and one-time stealth sub-address) and
+    /// independently proves ownership of it the same way a single receiver would
+    /// in the non-multisig flow: a DLEQ proof of knowledge of the scan secret
+    /// (never revealing it) plus a Schnorr proof of knowledge of `spend_secret +
+    /// H(shared_secret)`, both challenges bound to this signer so one signer's
+    /// proof can't be replayed to satisfy another's approval.
+    pub fn approve_zk_sweep(
+        ctx: Context<ApproveZkSweep>,
+        signer_index: u8,
         ownership_proof: Vec<u8>,
-        ephemeral_secret: [u8; 32],
     ) -> Result<()> {
         let pay_request = &mut ctx.accounts.pay_request;
-        let receiver = &ctx.accounts.receiver;
-        let escrow = &ctx.accounts.escrow;
 
-        // Verify ownership proof for stealth address
+        require!(pay_request.is_settled, ZkPaymentRequestError::NotSettled);
+        require!(!pay_request.is_swept, ZkPaymentRequestError::AlreadySwept);
+
+        let index = signer_index as usize;
+        require!(
+            index < pay_request.authorized_signers.len(),
+            ZkPaymentRequestError::InvalidSignerIndex
+        );
+        let signer_share = pay_request.authorized_signers[index].clone();
+        require!(
+            signer_share.approver == ctx.accounts.approver.key(),
+            ZkPaymentRequestError::UnauthorizedApprover
+        );
+
+        let bit = 1u16 << signer_index;
+        require!(
+            pay_request.approvals_bitmap & bit == 0,
+            ZkPaymentRequestError::AlreadyApproved
+        );
+
         verify_stealth_ownership(
-            &pay_request.stealth_address,
-            &pay_request.receiver,
+            &signer_share.stealth_address,
+            &signer_share.scan_pubkey,
+            &signer_share.spend_pubkey,
+            &signer_share.ephemeral_pubkey,
             pay_request.request_id,
+            &signer_share.approver,
             &ownership_proof,
-            &ephemeral_secret,
         )?;
 
-        // Verify receiver identity matches
-        require!(
-            pay_request.receiver == receiver.key(),
-            ZkPaymentRequestError::UnauthorizedReceiver
+        pay_request.approvals_bitmap |= bit;
+        pay_request.approved_count += 1;
+
+        msg!(
+            "ZK sweep approved by signer {}: {}/{} approvals",
+            signer_index,
+            pay_request.approved_count,
+            pay_request.threshold
         );
 
+        Ok(())
+    }
+
+    /// Sweep funds once `threshold` of the authorized signers have each approved
+    /// via `approve_zk_sweep`. Mirrors Zcash's multisig spend authorization: the
+    /// escrow PDA only releases funds once enough independent ownership proofs
+    /// have been recorded.
+    pub fn sweep_zk_funds(ctx: Context<SweepZkFunds>) -> Result<()> {
+        let pay_request = &mut ctx.accounts.pay_request;
+        let escrow = &ctx.accounts.escrow;
+
         // Check that payment has been settled but not yet swept
         require!(pay_request.is_settled, ZkPaymentRequestError::NotSettled);
         require!(!pay_request.is_swept, ZkPaymentRequestError::AlreadySwept);
 
+        require!(
+            pay_request.approved_count >= pay_request.threshold,
+            ZkPaymentRequestError::ThresholdNotMet
+        );
+
         let amount = pay_request.settled_amount;
 
         // Transfer all funds from escrow to receiver using invoke_signed
@@ -181,12 +297,156 @@ pub mod zk_payment_request {
             amount,
         )?;
 
-        // Store ownership proof and mark as swept
-        pay_request.ownership_proof = ownership_proof;
         pay_request.is_swept = true;
 
         msg!(
-            "ZK Funds swept with stealth address ownership proof"
+            "ZK Funds swept: {}/{} approvals reached threshold",
+            pay_request.approved_count,
+            pay_request.threshold
+        );
+
+        Ok(())
+    }
+
+    /// Create an oracle-conditioned (DLC-style) settlement over an integer outcome
+    /// domain `[0, base^num_digits)`. Each payout interval is compressed into the
+    /// minimal set of digit prefixes so the on-chain account only has to store
+    /// O(base * num_digits) entries instead of one per outcome.
+    pub fn create_oracle_settlement(
+        ctx: Context<CreateOracleSettlement>,
+        request_id: u64,
+        oracle_pubkey: Pubkey,
+        base: u8,
+        num_digits: u8,
+        intervals: Vec<PayoutInterval>,
+    ) -> Result<()> {
+        require!(base >= 2, ZkPaymentRequestError::InvalidOutcomeBase);
+        require!(num_digits > 0, ZkPaymentRequestError::InvalidOutcomeBase);
+
+        let domain = (base as u64)
+            .checked_pow(num_digits as u32)
+            .ok_or(ZkPaymentRequestError::InvalidOutcomeBase)?;
+
+        let mut prefixes = Vec::new();
+        let mut total_collateral = 0u64;
+        for interval in intervals.iter() {
+            require!(
+                interval.start <= interval.end && interval.end < domain,
+                ZkPaymentRequestError::InvalidPayoutInterval
+            );
+            decompose_interval(
+                interval.start,
+                interval.end,
+                base as u64,
+                num_digits,
+                interval.payout,
+                &mut prefixes,
+            )?;
+            total_collateral = total_collateral.max(interval.payout);
+        }
+
+        require!(
+            prefixes.len() <= MAX_PAYOUT_PREFIXES,
+            ZkPaymentRequestError::TooManyPayoutPrefixes
+        );
+
+        let settlement = &mut ctx.accounts.oracle_settlement;
+        settlement.receiver = ctx.accounts.receiver.key();
+        settlement.request_id = request_id;
+        settlement.oracle_pubkey = oracle_pubkey;
+        settlement.base = base;
+        settlement.num_digits = num_digits;
+        settlement.total_collateral = total_collateral;
+        settlement.prefixes = prefixes;
+        settlement.is_settled = false;
+        settlement.settled_outcome = 0;
+        settlement.settled_amount = 0;
+
+        msg!(
+            "Oracle settlement created: ID={}, collateral={}",
+            request_id,
+            total_collateral
+        );
+
+        Ok(())
+    }
+
+    /// Settle against a signed oracle outcome: the payer funds the escrow with the
+    /// full collateral, the program checks the Ed25519Program instruction
+    /// immediately preceding this one to confirm the oracle actually signed
+    /// `outcome`, then pays the receiver the amount assigned to the matching
+    /// digit-prefix interval and refunds the remainder to the payer.
+    pub fn settle_oracle_payment(ctx: Context<SettleOraclePayment>, outcome: u64) -> Result<()> {
+        let settlement = &mut ctx.accounts.oracle_settlement;
+        require!(!settlement.is_settled, ZkPaymentRequestError::AlreadySettled);
+
+        verify_oracle_signature(
+            &ctx.accounts.instructions.to_account_info(),
+            &settlement.oracle_pubkey,
+            outcome,
+        )?;
+
+        let payout = find_matching_prefix(
+            &settlement.prefixes,
+            outcome,
+            settlement.base as u64,
+            settlement.num_digits,
+        )
+        .ok_or(ZkPaymentRequestError::OutcomeNotCovered)?;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                },
+            ),
+            settlement.total_collateral,
+        )?;
+
+        let escrow_seeds = &[
+            b"oracle_escrow",
+            settlement.receiver.as_ref(),
+            &settlement.request_id.to_le_bytes(),
+            &[ctx.bumps.escrow],
+        ];
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    to: ctx.accounts.receiver.to_account_info(),
+                },
+                &[escrow_seeds],
+            ),
+            payout,
+        )?;
+
+        let refund = settlement.total_collateral - payout;
+        if refund > 0 {
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.escrow.to_account_info(),
+                        to: ctx.accounts.payer.to_account_info(),
+                    },
+                    &[escrow_seeds],
+                ),
+                refund,
+            )?;
+        }
+
+        settlement.is_settled = true;
+        settlement.settled_outcome = outcome;
+        settlement.settled_amount = payout;
+
+        msg!(
+            "Oracle settlement paid out: outcome={}, amount={}",
+            outcome,
+            payout
         );
 
         Ok(())
@@ -195,49 +455,119 @@ pub mod zk_payment_request {
 
 // Advanced ZK Cryptography Functions
 
-fn generate_stealth_address(
-    receiver_pubkey: &Pubkey,
-    request_id: u64,
-    ephemeral_pubkey: &[u8; 32],
-) -> Result<Pubkey> {
-    // Use HKDF to derive a stealth address from receiver pubkey + ephemeral key + request_id
-    let mut ikm = Vec::new();
-    ikm.extend_from_slice(receiver_pubkey.as_ref());
-    ikm.extend_from_slice(&request_id.to_le_bytes());
-    ikm.extend_from_slice(ephemeral_pubkey);
-
-    let hkdf = Hkdf::<Sha2_256>::new(None, &ikm);
-    let mut stealth_bytes = [0u8; 32];
-    hkdf.expand(b"stealth-address", &mut stealth_bytes)
+fn decompress_ristretto(bytes: &[u8; 32]) -> Result<RistrettoPoint> {
+    CompressedRistretto::from_slice(bytes)
+        .map_err(|_| ZkPaymentRequestError::InvalidStealthKey)?
+        .decompress()
+        .ok_or_else(|| ZkPaymentRequestError::InvalidStealthKey.into())
+}
+
+/// Reduce an ECDH shared point to the scalar `H(shared_point)` used to offset the
+/// spend key, via HKDF-over-SHA256 wide-reduced into the scalar field.
+fn hash_shared_secret_to_scalar(shared_point: &RistrettoPoint) -> Result<Scalar> {
+    let hkdf = Hkdf::<Sha2_256>::new(None, shared_point.compress().as_bytes());
+    let mut expanded = [0u8; 64];
+    hkdf.expand(b"stealth-shared-secret", &mut expanded)
         .map_err(|_| ZkPaymentRequestError::InvalidCommitment)?;
+    Ok(Scalar::from_bytes_mod_order_wide(&expanded))
+}
+
+/// Fiat-Shamir challenge for the Schnorr proof of knowledge of the one-time
+/// stealth private key, binding the proof to this specific request and to the
+/// signer presenting it so one approver's proof can't be replayed for another's.
+fn schnorr_challenge(
+    request_id: u64,
+    approver: &Pubkey,
+    nonce_point: &CompressedRistretto,
+    one_time_pub: &CompressedRistretto,
+) -> Scalar {
+    let mut hasher = Sha2_256::new();
+    hasher.update(b"zk-payment-request-stealth-ownership");
+    hasher.update(&request_id.to_le_bytes());
+    hasher.update(approver.as_ref());
+    hasher.update(nonce_point.as_bytes());
+    hasher.update(one_time_pub.as_bytes());
+    let mut wide = [0u8; 64];
+    wide[..32].copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order_wide(&wide)
+}
+
+/// Fiat-Shamir challenge for the Chaum-Pedersen DLEQ proof that the receiver knows
+/// a scalar `x` with `scan_pubkey = x*G` and `shared_point = x*ephemeral_pubkey`,
+/// binding the proof to this specific request and signer without ever revealing `x`.
+fn dleq_challenge(
+    request_id: u64,
+    approver: &Pubkey,
+    ephemeral_pubkey: &CompressedRistretto,
+    scan_pubkey: &CompressedRistretto,
+    shared_point: &CompressedRistretto,
+    t_g: &CompressedRistretto,
+    t_ephemeral: &CompressedRistretto,
+) -> Scalar {
+    let mut hasher = Sha2_256::new();
+    hasher.update(b"zk-payment-request-scan-dleq");
+    hasher.update(&request_id.to_le_bytes());
+    hasher.update(approver.as_ref());
+    hasher.update(ephemeral_pubkey.as_bytes());
+    hasher.update(scan_pubkey.as_bytes());
+    hasher.update(shared_point.as_bytes());
+    hasher.update(t_g.as_bytes());
+    hasher.update(t_ephemeral.as_bytes());
+    let mut wide = [0u8; 64];
+    wide[..32].copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order_wide(&wide)
+}
 
-    // Convert to Pubkey (simplified - in production use proper elliptic curve derivation)
-    Ok(Pubkey::new_from_array(stealth_bytes))
+/// Number of bits the bulletproof range proof must cover so that `max_amount`
+/// fits inside `[0, 2^bit_size)`. Bulletproofs require a power-of-two bit size.
+fn range_proof_bit_size(max_amount: u64) -> usize {
+    for bit_size in [8usize, 16, 32, 64] {
+        if bit_size == 64 || max_amount < (1u64 << bit_size) {
+            return bit_size;
+        }
+    }
+    64
 }
 
+/// Verifies that `amount_commitment` opens to a value in `[0, 2^bit_size)` for the
+/// `bit_size` that covers `max_amount`.
+///
+/// NOTE: the bulletproof itself only binds the committed value to `[0,
+/// 2^bit_size)`; `min_amount`/`max_amount` are mixed into the transcript purely as
+/// domain separation (so a proof can't be replayed against a different request or
+/// range) and are not range-proof bounds the proof enforces. A commitment to 0
+/// still verifies here even when `min_amount > 0` -- enforcing `min_amount` is left
+/// to the plaintext `amount >= pay_request.min_amount` check in `settle_zk_payment`.
 fn verify_bulletproof_range_proof(
     commitment: &[u8; 32],
     proof_bytes: &[u8],
     min_amount: u64,
     max_amount: u64,
+    request_id: u64,
 ) -> Result<()> {
-    // Verify bulletproof range proof
-    // In production, deserialize and verify the actual bulletproof
-    require!(proof_bytes.len() >= 64, ZkPaymentRequestError::InvalidProof);
     require!(min_amount < max_amount, ZkPaymentRequestError::InvalidRange);
 
-    // Simplified verification - check proof structure
-    let mut hasher = Sha3_256::new();
-    hasher.update(commitment);
-    hasher.update(&min_amount.to_le_bytes());
-    hasher.update(&max_amount.to_le_bytes());
-    hasher.update(proof_bytes);
-    let verification_hash = hasher.finalize();
+    let bit_size = range_proof_bit_size(max_amount);
 
-    require!(
-        verification_hash.iter().filter(|&&x| x != 0).count() > 16,
-        ZkPaymentRequestError::InvalidProof
-    );
+    let range_proof =
+        RangeProof::from_bytes(proof_bytes).map_err(|_| ZkPaymentRequestError::InvalidProof)?;
+
+    let committed_value = CompressedRistretto::from_slice(commitment)
+        .map_err(|_| ZkPaymentRequestError::InvalidCommitment)?;
+
+    let pc_gens = PedersenGens::default();
+    let bp_gens = BulletproofGens::new(bit_size, 1);
+
+    // Domain-separate the transcript so a proof can't be replayed against a
+    // different request or range.
+    let mut transcript = Transcript::new(b"zk-payment-request-range-proof");
+    transcript.append_u64(b"request_id", request_id);
+    transcript.append_u64(b"min_amount", min_amount);
+    transcript.append_u64(b"max_amount", max_amount);
+
+    range_proof
+        .verify_single(&bp_gens, &pc_gens, &mut transcript, &committed_value, bit_size)
+        .map_err(|_| ZkPaymentRequestError::InvalidProof)?;
 
     Ok(())
 }
@@ -245,73 +575,355 @@ fn verify_bulletproof_range_proof(
 fn verify_bulletproof_payment(
     commitment: &[u8; 32],
     amount: u64,
-    proof_bytes: &[u8],
+    payment_proof: &[u8],
 ) -> Result<()> {
-    // Verify that the amount matches the commitment using bulletproof
-    require!(proof_bytes.len() >= 64, ZkPaymentRequestError::InvalidPaymentProof);
+    // `payment_proof` carries the Pedersen blinding factor the payer used
+    // when they committed to `amount`, so the receiver (and the program) can
+    // check that the revealed amount actually opens the stored commitment.
+    require!(payment_proof.len() >= 32, ZkPaymentRequestError::InvalidPaymentProof);
 
-    // Create verification data
-    let mut verification_data = Vec::new();
-    verification_data.extend_from_slice(&amount.to_le_bytes());
-    verification_data.extend_from_slice(proof_bytes);
-    verification_data.extend_from_slice(b"bulletproof_payment");
+    let mut blinding_bytes = [0u8; 32];
+    blinding_bytes.copy_from_slice(&payment_proof[..32]);
+    let blinding: Scalar = Option::from(Scalar::from_canonical_bytes(blinding_bytes))
+        .ok_or(ZkPaymentRequestError::InvalidPaymentProof)?;
 
-    let mut hasher = Sha2_256::new();
-    hasher.update(&verification_data);
-    let hash_output = hasher.finalize();
+    let stored_commitment = CompressedRistretto::from_slice(commitment)
+        .map_err(|_| ZkPaymentRequestError::InvalidCommitment)?;
+
+    let pc_gens = PedersenGens::default();
+    let opened_commitment = pc_gens.commit(Scalar::from(amount), blinding).compress();
 
     require!(
-        hash_output.as_slice() == commitment.as_slice(),
+        opened_commitment == stored_commitment,
         ZkPaymentRequestError::InvalidPaymentProof
     );
 
     Ok(())
 }
 
+/// Verify that the caller knows the receiver's `scan_secret` for the published
+/// `scan_pubkey` -- via a Chaum-Pedersen DLEQ proof that the same scalar opens both
+/// `scan_pubkey = x*G` and the per-request shared point `x*ephemeral_pubkey`, rather
+/// than revealing `x` itself -- that the resulting shared secret reconstructs the
+/// one-time `stealth_address`, and that `ownership_proof` also carries a valid
+/// Schnorr proof of knowledge of the corresponding one-time private key
+/// `spend_secret + H(shared_secret)`. Neither the scan key nor the spend key is ever
+/// revealed, so approving a sweep does not let anyone link the receiver's other
+/// payments the way publishing `scan_secret` on-chain would.
+///
+/// `ownership_proof` layout (192 bytes): `shared_point (32) || dleq_t_g (32) ||
+/// dleq_t_ephemeral (32) || dleq_response (32) || schnorr_nonce (32) ||
+/// schnorr_response (32)`. Both challenges are bound to `approver` so this proof
+/// is only valid for the signer presenting it, not replayable against a co-signer
+/// with a different key share.
 fn verify_stealth_ownership(
     stealth_address: &Pubkey,
-    receiver_pubkey: &Pubkey,
+    scan_pubkey: &[u8; 32],
+    spend_pubkey: &[u8; 32],
+    ephemeral_pubkey: &[u8; 32],
     request_id: u64,
+    approver: &Pubkey,
     ownership_proof: &[u8],
-    ephemeral_secret: &[u8; 32],
 ) -> Result<()> {
-    // Verify that the receiver can derive the stealth address
-    let computed_stealth = generate_stealth_address(
-        receiver_pubkey,
+    require!(ownership_proof.len() == 192, ZkPaymentRequestError::InvalidReceiverProof);
+
+    let shared_point_compressed = CompressedRistretto::from_slice(&ownership_proof[0..32])
+        .map_err(|_| ZkPaymentRequestError::InvalidReceiverProof)?;
+    let t_g = CompressedRistretto::from_slice(&ownership_proof[32..64])
+        .map_err(|_| ZkPaymentRequestError::InvalidReceiverProof)?;
+    let t_ephemeral = CompressedRistretto::from_slice(&ownership_proof[64..96])
+        .map_err(|_| ZkPaymentRequestError::InvalidReceiverProof)?;
+    let dleq_response: Scalar = Option::from(Scalar::from_canonical_bytes(
+        ownership_proof[96..128]
+            .try_into()
+            .map_err(|_| ZkPaymentRequestError::InvalidReceiverProof)?,
+    ))
+    .ok_or(ZkPaymentRequestError::InvalidReceiverProof)?;
+
+    let scan_pub_compressed = CompressedRistretto::from_slice(scan_pubkey)
+        .map_err(|_| ZkPaymentRequestError::InvalidStealthKey)?;
+    let scan_pub_point = scan_pub_compressed
+        .decompress()
+        .ok_or(ZkPaymentRequestError::InvalidStealthKey)?;
+    let ephemeral_compressed = CompressedRistretto::from_slice(ephemeral_pubkey)
+        .map_err(|_| ZkPaymentRequestError::InvalidStealthKey)?;
+    let ephemeral_point = ephemeral_compressed
+        .decompress()
+        .ok_or(ZkPaymentRequestError::InvalidStealthKey)?;
+    let shared_point = shared_point_compressed
+        .decompress()
+        .ok_or(ZkPaymentRequestError::InvalidReceiverProof)?;
+    let t_g_point = t_g.decompress().ok_or(ZkPaymentRequestError::InvalidReceiverProof)?;
+    let t_ephemeral_point = t_ephemeral
+        .decompress()
+        .ok_or(ZkPaymentRequestError::InvalidReceiverProof)?;
+
+    let dleq_challenge = dleq_challenge(
         request_id,
-        ephemeral_secret,
-    )?;
+        approver,
+        &ephemeral_compressed,
+        &scan_pub_compressed,
+        &shared_point_compressed,
+        &t_g,
+        &t_ephemeral,
+    );
+
+    // Chaum-Pedersen verification: dleq_response*G == t_g + c*scan_pubkey and
+    // dleq_response*ephemeral_pubkey == t_ephemeral + c*shared_point prove knowledge
+    // of a single scalar satisfying both relations, without revealing it.
+    require!(
+        dleq_response * RISTRETTO_BASEPOINT_POINT == t_g_point + dleq_challenge * scan_pub_point,
+        ZkPaymentRequestError::UnauthorizedReceiver
+    );
+    require!(
+        dleq_response * ephemeral_point == t_ephemeral_point + dleq_challenge * shared_point,
+        ZkPaymentRequestError::UnauthorizedReceiver
+    );
+
+    let h = hash_shared_secret_to_scalar(&shared_point)?;
+
+    let spend_pub_point = decompress_ristretto(spend_pubkey)?;
+    let expected_one_time_point = spend_pub_point + h * RISTRETTO_BASEPOINT_POINT;
+    let expected_one_time_compressed = expected_one_time_point.compress();
 
     require!(
-        computed_stealth == *stealth_address,
+        expected_one_time_compressed.as_bytes() == stealth_address.as_ref(),
         ZkPaymentRequestError::UnauthorizedReceiver
     );
 
-    // Verify ownership proof
-    require!(ownership_proof.len() >= 32, ZkPaymentRequestError::InvalidReceiverProof);
+    // Verify the Schnorr proof of knowledge of (spend_secret + h) for the one-time address.
+    let nonce_point = CompressedRistretto::from_slice(&ownership_proof[128..160])
+        .map_err(|_| ZkPaymentRequestError::InvalidReceiverProof)?;
+    let response: Scalar = Option::from(Scalar::from_canonical_bytes(
+        ownership_proof[160..192]
+            .try_into()
+            .map_err(|_| ZkPaymentRequestError::InvalidReceiverProof)?,
+    ))
+    .ok_or(ZkPaymentRequestError::InvalidReceiverProof)?;
 
-    let mut proof_data = Vec::new();
-    proof_data.extend_from_slice(receiver_pubkey.as_ref());
-    proof_data.extend_from_slice(&request_id.to_le_bytes());
-    proof_data.extend_from_slice(ephemeral_secret);
-    proof_data.extend_from_slice(ownership_proof);
+    let challenge = schnorr_challenge(request_id, approver, &nonce_point, &expected_one_time_compressed);
 
-    let mut hasher = Sha2_256::new();
-    hasher.update(&proof_data);
-    let hash_output = hasher.finalize();
-    let hash_slice: &[u8] = hash_output.as_ref();
+    let nonce = nonce_point
+        .decompress()
+        .ok_or(ZkPaymentRequestError::InvalidReceiverProof)?;
 
-    // Check that proof is valid (non-trivial)
     require!(
-        hash_slice.iter().filter(|&&x| x != 0).count() > 20,
+        response * RISTRETTO_BASEPOINT_POINT == nonce + challenge * expected_one_time_point,
         ZkPaymentRequestError::InvalidReceiverProof
     );
 
     Ok(())
 }
 
+// Oracle-conditioned (DLC-style) settlement: digit-decomposition payout curves.
+
+/// Max number of digit prefixes an `OracleSettlement` account can hold. Bounds
+/// `ZkPayRequest`-style account space the same way `amount_range_proof` is capped.
+const MAX_PAYOUT_PREFIXES: usize = 64;
+
+/// Max digits a payout-curve outcome domain can have; keeps `digits` within a
+/// fixed-size budget and the per-level recursion in `decompose_interval` shallow.
+const MAX_OUTCOME_DIGITS: u8 = 20;
+
+/// Compress the interval `[start, end]` (inclusive, in base `base` over `num_digits`
+/// digits) into the minimal set of digit-prefix groups: at each level, a range that
+/// exactly spans one aligned `base^level`-sized block collapses to a single prefix;
+/// otherwise the unaligned low portion is handled one digit level down and the
+/// remaining high portion recurses at the same level.
+fn decompose_interval(
+    start: u64,
+    end: u64,
+    base: u64,
+    num_digits: u8,
+    payout: u64,
+    out: &mut Vec<PayoutPrefix>,
+) -> Result<()> {
+    require!(num_digits <= MAX_OUTCOME_DIGITS, ZkPaymentRequestError::InvalidOutcomeBase);
+    decompose_level(start, end, base, num_digits, num_digits, payout, out)
+}
+
+fn decompose_level(
+    start: u64,
+    end: u64,
+    base: u64,
+    num_digits: u8,
+    level: u8,
+    payout: u64,
+    out: &mut Vec<PayoutPrefix>,
+) -> Result<()> {
+    if start > end {
+        return Ok(());
+    }
+
+    // No special case for `level == 0`: `block = base^0 = 1` already makes the
+    // aligned branch below match `[start, start]` exactly and recurse one outcome
+    // at a time, so it enumerates the remainder correctly on its own.
+    let block = base
+        .checked_pow(level as u32)
+        .ok_or(ZkPaymentRequestError::InvalidOutcomeBase)?;
+    let block_start = (start / block) * block;
+    let block_end = block_start + block - 1;
+
+    if start == block_start && end >= block_end {
+        let prefix_value = block_start / block;
+        out.push(PayoutPrefix {
+            digits: digits_of(prefix_value, num_digits - level, base),
+            payout,
+        });
+
+        let next_start = block_end + 1;
+        if next_start <= end {
+            decompose_level(next_start, end, base, num_digits, level, payout, out)?;
+        }
+    } else {
+        let low_end = core::cmp::min(end, block_end);
+        decompose_level(start, low_end, base, num_digits, level - 1, payout, out)?;
+        if low_end < end {
+            decompose_level(low_end + 1, end, base, num_digits, level, payout, out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Most-significant-digit-first base-`base` representation of `value`, padded/truncated
+/// to `len` digits (e.g. `digits_of(12, 2, 10) == [1, 2]`).
+fn digits_of(mut value: u64, len: u8, base: u64) -> Vec<u8> {
+    let mut digits = vec![0u8; len as usize];
+    for i in (0..len as usize).rev() {
+        digits[i] = (value % base) as u8;
+        value /= base;
+    }
+    digits
+}
+
+/// Find the payout whose stored prefix matches the outcome's leading digits.
+fn find_matching_prefix(
+    prefixes: &[PayoutPrefix],
+    outcome: u64,
+    base: u64,
+    num_digits: u8,
+) -> Option<u64> {
+    let outcome_digits = digits_of(outcome, num_digits, base);
+    prefixes
+        .iter()
+        .find(|prefix| outcome_digits.starts_with(&prefix.digits))
+        .map(|prefix| prefix.payout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decompose `[start, end]` and assert every outcome in it -- including ones
+    /// that don't land on a `base^k`-aligned boundary -- resolves to `payout`.
+    fn assert_interval_fully_covered(start: u64, end: u64, base: u64, num_digits: u8, payout: u64) {
+        let mut prefixes = Vec::new();
+        decompose_interval(start, end, base, num_digits, payout, &mut prefixes).unwrap();
+
+        for outcome in start..=end {
+            assert_eq!(
+                find_matching_prefix(&prefixes, outcome, base, num_digits),
+                Some(payout),
+                "outcome {} in [{}, {}] (base {}) matched no prefix",
+                outcome,
+                start,
+                end,
+                base
+            );
+        }
+    }
+
+    #[test]
+    fn decompose_interval_covers_unaligned_ranges_base10() {
+        assert_interval_fully_covered(3, 12, 10, 2, 1_000);
+        assert_interval_fully_covered(13, 27, 10, 2, 1_000);
+        assert_interval_fully_covered(1, 2, 10, 2, 1_000);
+        assert_interval_fully_covered(40, 59, 10, 2, 1_000);
+        assert_interval_fully_covered(0, 99, 10, 2, 1_000);
+    }
+
+    #[test]
+    fn decompose_interval_covers_unaligned_ranges_base2() {
+        assert_interval_fully_covered(1, 6, 2, 3, 500);
+        assert_interval_fully_covered(0, 7, 2, 3, 500);
+    }
+}
+
+/// Verify that the Ed25519Program instruction immediately preceding this one in the
+/// transaction is a valid signature by `oracle_pubkey` over `outcome`'s little-endian
+/// bytes -- the standard Solana idiom for checking an externally-signed message
+/// on-chain via precompile instruction introspection.
+fn verify_oracle_signature(
+    instructions_sysvar: &AccountInfo,
+    oracle_pubkey: &Pubkey,
+    outcome: u64,
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    require!(current_index > 0, ZkPaymentRequestError::MissingOracleSignature);
+
+    let ed25519_ix = load_instruction_at_checked(current_index - 1, instructions_sysvar)?;
+    require!(
+        ed25519_ix.program_id == ed25519_program::ID,
+        ZkPaymentRequestError::MissingOracleSignature
+    );
+
+    // Ed25519SignatureOffsets: 1 byte count + 1 byte padding, then 7 little-endian u16
+    // fields (signature/pubkey/message offsets and instruction indices).
+    let data = &ed25519_ix.data;
+    require!(data.len() >= 16, ZkPaymentRequestError::InvalidOracleSignature);
+    require!(data[0] == 1, ZkPaymentRequestError::InvalidOracleSignature);
+
+    let signature_ix_index = u16::from_le_bytes([data[4], data[5]]);
+    let pubkey_offset = u16::from_le_bytes([data[6], data[7]]) as usize;
+    let pubkey_ix_index = u16::from_le_bytes([data[8], data[9]]);
+    let message_offset = u16::from_le_bytes([data[10], data[11]]) as usize;
+    let message_size = u16::from_le_bytes([data[12], data[13]]) as usize;
+    let message_ix_index = u16::from_le_bytes([data[14], data[15]]);
+
+    // Every offset must resolve within *this* Ed25519 instruction (the standard
+    // `new_ed25519_instruction` builder sets all three index fields to u16::MAX to
+    // mean "this instruction"). Otherwise a payer could point them at an unrelated
+    // instruction elsewhere in the transaction and smuggle in a self-signed message.
+    require!(
+        signature_ix_index == u16::MAX
+            && pubkey_ix_index == u16::MAX
+            && message_ix_index == u16::MAX,
+        ZkPaymentRequestError::InvalidOracleSignature
+    );
+
+    require!(
+        data.len() >= pubkey_offset + 32,
+        ZkPaymentRequestError::InvalidOracleSignature
+    );
+    require!(
+        &data[pubkey_offset..pubkey_offset + 32] == oracle_pubkey.as_ref(),
+        ZkPaymentRequestError::UntrustedOracle
+    );
+
+    require!(
+        data.len() >= message_offset + message_size,
+        ZkPaymentRequestError::InvalidOracleSignature
+    );
+    require!(
+        data[message_offset..message_offset + message_size] == outcome.to_le_bytes(),
+        ZkPaymentRequestError::OracleOutcomeMismatch
+    );
+
+    Ok(())
+}
+
+/// Emitted when a request's stealth address payload is jumbled, so clients can
+/// bech32m-encode `jumbled_address` as the checksummed unified address instead of
+/// concatenating `scan_pubkey`/`spend_pubkey`/`ephemeral_pubkey` directly.
+#[event]
+pub struct StealthAddressEmitted {
+    pub request_id: u64,
+    pub jumbled_address: Vec<u8>,
+}
+
 #[derive(Accounts)]
-#[instruction(request_id: u64, ephemeral_pubkey: [u8; 32])]
+#[instruction(request_id: u64)]
 pub struct CreateZkPayRequest<'info> {
     #[account(
         init,
@@ -360,7 +972,19 @@ pub struct SettleZkPayment<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(ownership_proof: Vec<u8>, ephemeral_secret: [u8; 32])]
+#[instruction(signer_index: u8)]
+pub struct ApproveZkSweep<'info> {
+    #[account(
+        mut,
+        seeds = [b"zk_pay_request", pay_request.receiver.as_ref(), &pay_request.request_id.to_le_bytes()],
+        bump,
+    )]
+    pub pay_request: Account<'info, ZkPayRequest>,
+
+    pub approver: Signer<'info>,
+}
+
+#[derive(Accounts)]
 pub struct SweepZkFunds<'info> {
     #[account(
         mut,
@@ -378,24 +1002,82 @@ pub struct SweepZkFunds<'info> {
     )]
     pub escrow: UncheckedAccount<'info>,
 
+    /// CHECK: Payout destination; must match the request's stored receiver. Any
+    /// approved-threshold caller (not necessarily `receiver` itself) can trigger the sweep.
+    #[account(mut, address = pay_request.receiver)]
+    pub receiver: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(request_id: u64)]
+pub struct CreateOracleSettlement<'info> {
+    #[account(
+        init,
+        payer = receiver,
+        space = OracleSettlement::LEN,
+        seeds = [b"oracle_settlement", receiver.key().as_ref(), &request_id.to_le_bytes()],
+        bump
+    )]
+    pub oracle_settlement: Account<'info, OracleSettlement>,
+
     #[account(mut)]
     pub receiver: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct SettleOraclePayment<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle_settlement", oracle_settlement.receiver.as_ref(), &oracle_settlement.request_id.to_le_bytes()],
+        bump,
+    )]
+    pub oracle_settlement: Account<'info, OracleSettlement>,
+
+    /// CHECK: This is a PDA that holds the escrow funds
+    #[account(
+        mut,
+        seeds = [b"oracle_escrow", oracle_settlement.receiver.as_ref(), &oracle_settlement.request_id.to_le_bytes()],
+        bump,
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// CHECK: Verified against `oracle_settlement.receiver` before any transfer.
+    #[account(mut, address = oracle_settlement.receiver)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, read to find the preceding Ed25519Program instruction.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[account]
 pub struct ZkPayRequest {
     pub receiver: Pubkey,
     pub request_id: u64,
     pub amount_commitment: [u8; 32],           // Pedersen commitment to amount
     pub amount_range_proof: Vec<u8>,           // Bulletproof range proof
-    pub stealth_address: Pubkey,               // One-time stealth address
+    pub scan_pubkey: [u8; 32],                 // Receiver's published scan public key
+    pub spend_pubkey: [u8; 32],                // Receiver's published spend public key
+    pub ephemeral_pubkey: [u8; 32],            // Payer's one-time ephemeral public key (r*G)
+    pub stealth_address: Pubkey,               // One-time stealth address (spend_pub + H(r*scan_pub)*G)
+    pub encrypted_memo: [u8; 580],             // ChaCha20-Poly1305 ciphertext of a padded memo note
+    pub authorized_signers: Vec<AuthorizedSigner>, // m-of-n multisig receiver key shares
+    pub threshold: u8,                         // m: number of approvals required to sweep
+    pub approvals_bitmap: u16,                 // bit i set once authorized_signers[i] has approved
+    pub approved_count: u8,
     pub min_amount: u64,                       // Minimum amount in range
     pub max_amount: u64,                       // Maximum amount in range
     pub settled_amount: u64,                   // Actual settled amount (hidden)
     pub settlement_commitment: [u8; 32],       // Commitment to settlement details
-    pub ownership_proof: Vec<u8>,              // ZK proof of ownership
     pub is_settled: bool,
     pub is_swept: bool,
 }
@@ -405,17 +1087,99 @@ impl ZkPayRequest {
         32 + // receiver: Pubkey
         8 + // request_id: u64
         32 + // amount_commitment: [u8; 32]
-        4 + 512 + // amount_range_proof: Vec<u8> (4 bytes len + up to 512 bytes for bulletproof)
+        4 + MAX_RANGE_PROOF_LEN + // amount_range_proof: Vec<u8> (dalek bulletproof, up to 672 bytes for a 64-bit range)
+        32 + // scan_pubkey: [u8; 32]
+        32 + // spend_pubkey: [u8; 32]
+        32 + // ephemeral_pubkey: [u8; 32]
         32 + // stealth_address: Pubkey
+        580 + // encrypted_memo: [u8; 580]
+        4 + MAX_AUTHORIZED_SIGNERS * AuthorizedSigner::LEN + // authorized_signers: Vec<AuthorizedSigner>
+        1 + // threshold: u8
+        2 + // approvals_bitmap: u16
+        1 + // approved_count: u8
         8 + // min_amount: u64
         8 + // max_amount: u64
         8 + // settled_amount: u64
         32 + // settlement_commitment: [u8; 32]
-        4 + 256 + // ownership_proof: Vec<u8> (4 bytes len + up to 256 bytes)
         1 + // is_settled: bool
         1; // is_swept: bool
 }
 
+/// Max number of co-signers an `m`-of-`n` `ZkPayRequest` receiver can have; bounds
+/// account space and keeps every signer addressable by a bit in `approvals_bitmap`.
+const MAX_AUTHORIZED_SIGNERS: usize = 16;
+
+/// One co-signer's entry in an `m`-of-`n` `ZkPayRequest`: the Solana key that must
+/// sign `approve_zk_sweep`, plus that signer's own stealth key share -- a distinct
+/// scan/spend/ephemeral triple and one-time sub-address, independent of every other
+/// signer's -- so each approval is `verify_stealth_ownership`'d against key material
+/// only that signer can know.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AuthorizedSigner {
+    pub approver: Pubkey,
+    pub scan_pubkey: [u8; 32],
+    pub spend_pubkey: [u8; 32],
+    pub ephemeral_pubkey: [u8; 32],
+    pub stealth_address: Pubkey,
+}
+
+impl AuthorizedSigner {
+    pub const LEN: usize = 32 + // approver: Pubkey
+        32 + // scan_pubkey: [u8; 32]
+        32 + // spend_pubkey: [u8; 32]
+        32 + // ephemeral_pubkey: [u8; 32]
+        32; // stealth_address: Pubkey
+}
+
+/// Max serialized size of a dalek `RangeProof`: a 64-bit range proof (the largest
+/// `range_proof_bit_size` can select) is 672 bytes; smaller bit sizes are shorter.
+const MAX_RANGE_PROOF_LEN: usize = 672;
+
+/// A receiver-supplied payout interval over the outcome domain, e.g. "outcome in
+/// [40, 59] pays 1_000_000 lamports".
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PayoutInterval {
+    pub start: u64,
+    pub end: u64,
+    pub payout: u64,
+}
+
+/// A digit prefix produced by [`decompose_interval`]: any outcome whose leading
+/// `digits.len()` base-`base` digits equal `digits` is paid `payout`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PayoutPrefix {
+    pub digits: Vec<u8>,
+    pub payout: u64,
+}
+
+#[account]
+pub struct OracleSettlement {
+    pub receiver: Pubkey,
+    pub request_id: u64,
+    pub oracle_pubkey: Pubkey,          // Key that must sign the outcome
+    pub base: u8,                       // Outcome digit base
+    pub num_digits: u8,                 // Outcome domain is [0, base^num_digits)
+    pub total_collateral: u64,          // Max payout across all intervals; funded at settlement
+    pub prefixes: Vec<PayoutPrefix>,    // Digit-decomposed payout curve
+    pub is_settled: bool,
+    pub settled_outcome: u64,
+    pub settled_amount: u64,
+}
+
+impl OracleSettlement {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // receiver: Pubkey
+        8 + // request_id: u64
+        32 + // oracle_pubkey: Pubkey
+        1 + // base: u8
+        1 + // num_digits: u8
+        8 + // total_collateral: u64
+        4 + MAX_PAYOUT_PREFIXES * (4 + MAX_OUTCOME_DIGITS as usize + 8) + // prefixes: Vec<PayoutPrefix>
+        1 + // is_settled: bool
+        8 + // settled_outcome: u64
+        8; // settled_amount: u64
+}
+
 #[error_code]
 pub enum ZkPaymentRequestError {
     #[msg("ZK Payment request has already been settled")]
@@ -430,6 +1194,8 @@ pub enum ZkPaymentRequestError {
     InvalidProof,
     #[msg("Invalid amount range specified")]
     InvalidRange,
+    #[msg("Range proof is larger than the reserved account space")]
+    RangeProofTooLarge,
     #[msg("Invalid ZK commitment")]
     InvalidCommitment,
     #[msg("Invalid payment proof")]
@@ -438,4 +1204,32 @@ pub enum ZkPaymentRequestError {
     InvalidReceiverProof,
     #[msg("Amount is outside the committed range")]
     AmountOutOfRange,
+    #[msg("Invalid stealth key: not a valid curve point")]
+    InvalidStealthKey,
+    #[msg("Invalid outcome base or digit count")]
+    InvalidOutcomeBase,
+    #[msg("Payout interval is out of the outcome domain")]
+    InvalidPayoutInterval,
+    #[msg("Too many payout prefixes for this outcome domain")]
+    TooManyPayoutPrefixes,
+    #[msg("Settlement outcome is not covered by any payout interval")]
+    OutcomeNotCovered,
+    #[msg("Expected an Ed25519Program instruction signing the oracle outcome")]
+    MissingOracleSignature,
+    #[msg("Malformed Ed25519Program instruction data")]
+    InvalidOracleSignature,
+    #[msg("Ed25519 signature is not from the expected oracle")]
+    UntrustedOracle,
+    #[msg("Signed oracle message does not match the submitted outcome")]
+    OracleOutcomeMismatch,
+    #[msg("Invalid multisig threshold or signer set")]
+    InvalidThreshold,
+    #[msg("Signer index is out of range for this request's authorized signers")]
+    InvalidSignerIndex,
+    #[msg("This authorized signer has already approved the sweep")]
+    AlreadyApproved,
+    #[msg("Not enough authorized signers have approved the sweep yet")]
+    ThresholdNotMet,
+    #[msg("Approver is not the authorized signer at this index")]
+    UnauthorizedApprover,
 }